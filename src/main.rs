@@ -1,64 +1,133 @@
 use glam::DVec3;
-use indicatif::ProgressIterator;
+use image::{ImageBuffer, Rgb};
+use indicatif::ParallelProgressIterator;
 use itertools::Itertools;
 use rand::prelude::*;
-use std::{fs, io, ops::Range};
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+use std::{fs, io, ops::Range, path::Path};
 
 fn main() -> io::Result<()> {
-    let mut world = HittableList { objects: vec![] };
-
-    let material_ground = Material::Lambertian {
-        albedo: DVec3::new(0.8, 0.8, 0.0),
-    };
-    let material_center = Material::Lambertian {
-        albedo: DVec3::new(0.1, 0.2, 0.5),
-    };
-    let material_left = Material::Dielectric {
-        index_of_refraction: 1.5,
-    };
-    let material_right = Material::Metal {
-        albedo: DVec3::new(0.8, 0.6, 0.2),
-        fuzz: 0.0,
-    };
-
-    world.add(Sphere {
-        center: DVec3::new(0.0, -100.5, -1.0),
-        radius: 100.0,
-        material: material_ground,
-    });
-    world.add(Sphere {
-        center: DVec3::new(0.0, 0.0, -1.0),
-        radius: 0.5,
-        material: material_center,
-    });
-    world.add(Sphere {
-        center: DVec3::new(-1.0, 0.0, -1.0),
-        radius: 0.5,
-        material: material_left.clone(),
-    });
-    world.add(Sphere {
-        center: DVec3::new(-1.0, 0.0, -1.0),
-        radius: -0.4,
-        material: material_left,
-    });
-    world.add(Sphere {
-        center: DVec3::new(1.0, 0.0, -1.0),
-        radius: 0.5,
-        material: material_right,
-    });
+    // Seed the RNG explicitly so the capstone scene's layout is reproducible.
+    // The per-pixel sampling in `render_to_disk` is seeded from entropy, so
+    // the sampling noise still differs between renders.
+    let mut rng = StdRng::seed_from_u64(0);
+    let world = random_scene(&mut rng);
+    // Wrap the scene in a BVH so the hundreds of spheres intersect in O(log N).
+    let world = BvhNode::new(world.objects);
 
+    // Shutter open for the same interval the moving spheres above animate
+    // across, so the final render actually shows their motion blur.
     let camera = Camera::new(
-        400,
-        16.0 / 9.0,
-        Some(DVec3::new(-2., 2., 1.)),
-        Some(DVec3::new(0., 0., -1.)),
-        Some(DVec3::Y),
+        CameraConfig::default()
+            .image_width(1200)
+            .aspect_ratio(16.0 / 9.0)
+            .look_from(DVec3::new(13., 2., 3.))
+            .look_at(DVec3::new(0., 0., 0.))
+            .vup(DVec3::Y)
+            .aperture(0.1)
+            .focus_dist(10.0)
+            .shutter(0.0, 1.0),
+    );
+    camera.render_to_disk(world, "output.png")?;
+
+    // A second, black-background scene lit only by a `DiffuseLight` sphere,
+    // demonstrating the emissive-material path instead of the sky gradient.
+    let lit_world = emissive_scene();
+    let lit_camera = Camera::still(
+        CameraConfig::default()
+            .image_width(400)
+            .aspect_ratio(16.0 / 9.0)
+            .look_from(DVec3::new(26., 3., 6.))
+            .look_at(DVec3::new(0., 2., 0.))
+            .vup(DVec3::Y)
+            .background(DVec3::ZERO),
     );
-    camera.render_to_disk(world)?;
+    lit_camera.render_to_disk(lit_world, "emissive.png")?;
 
     Ok(())
 }
 
+/// Grouped, named parameters for building a [`Camera`]. Several of the
+/// underlying fields are interchangeable `f64`s (`aperture`, `focus_dist`,
+/// the shutter times), so a positional constructor is error-prone to call
+/// correctly; this builder lets callers set only what they need by name and
+/// leaves everything else at the pinhole/static-shutter default.
+struct CameraConfig {
+    image_width: u32,
+    aspect_ratio: f64,
+    look_from: DVec3,
+    look_at: DVec3,
+    vup: DVec3,
+    aperture: f64,
+    focus_dist: Option<f64>,
+    time0: f64,
+    time1: f64,
+    background: Option<DVec3>,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            image_width: 400,
+            aspect_ratio: 16.0 / 9.0,
+            look_from: DVec3::NEG_Z,
+            look_at: DVec3::ZERO,
+            vup: DVec3::Y,
+            aperture: 0.0,
+            focus_dist: None,
+            time0: 0.0,
+            time1: 0.0,
+            background: None,
+        }
+    }
+}
+
+impl CameraConfig {
+    fn image_width(mut self, image_width: u32) -> Self {
+        self.image_width = image_width;
+        self
+    }
+    fn aspect_ratio(mut self, aspect_ratio: f64) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+    fn look_from(mut self, look_from: DVec3) -> Self {
+        self.look_from = look_from;
+        self
+    }
+    fn look_at(mut self, look_at: DVec3) -> Self {
+        self.look_at = look_at;
+        self
+    }
+    fn vup(mut self, vup: DVec3) -> Self {
+        self.vup = vup;
+        self
+    }
+    fn aperture(mut self, aperture: f64) -> Self {
+        self.aperture = aperture;
+        self
+    }
+    /// Distance to the plane that is in perfect focus; if left unset it
+    /// defaults to the distance between `look_from` and `look_at`.
+    fn focus_dist(mut self, focus_dist: f64) -> Self {
+        self.focus_dist = Some(focus_dist);
+        self
+    }
+    /// Shutter open/close times, for motion blur; `0.0..0.0` (the default)
+    /// pins every ray to a single instant.
+    fn shutter(mut self, time0: f64, time1: f64) -> Self {
+        self.time0 = time0;
+        self.time1 = time1;
+        self
+    }
+    /// Solid background color; unset falls back to the blue-white sky gradient.
+    fn background(mut self, background: DVec3) -> Self {
+        self.background = Some(background);
+        self
+    }
+}
+
 /// Hidden docs are calculated fields
 struct Camera {
     /// Rendered image width in pixel count
@@ -98,27 +167,48 @@ struct Camera {
     v: DVec3,
     #[doc(hidden)]
     w: DVec3,
+
+    /// Radius of the defocus (lens) disk; `0.0` means a pinhole camera
+    #[doc(hidden)]
+    lens_radius: f64,
+    #[doc(hidden)]
+    defocus_disk_u: DVec3,
+    #[doc(hidden)]
+    defocus_disk_v: DVec3,
+
+    /// Shutter open time
+    time0: f64,
+    /// Shutter close time
+    time1: f64,
+
+    /// Solid background color; `None` uses the blue-white sky gradient
+    background: Option<DVec3>,
 }
 impl Camera {
-    fn new(
-        image_width: u32,
-        aspect_ratio: f64,
-        look_from: Option<DVec3>,
-        look_at: Option<DVec3>,
-        vup: Option<DVec3>,
-    ) -> Self {
-        let lookfrom = look_from.unwrap_or(DVec3::NEG_Z);
-        let lookat = look_at.unwrap_or(DVec3::ZERO);
-        let vup = vup.unwrap_or(DVec3::Y);
+    fn new(config: CameraConfig) -> Self {
+        let CameraConfig {
+            image_width,
+            aspect_ratio,
+            look_from: lookfrom,
+            look_at: lookat,
+            vup,
+            aperture,
+            focus_dist,
+            time0,
+            time1,
+            background,
+        } = config;
+        // Distance to the plane that is in perfect focus; defaults to the
+        // subject distance so a zero aperture behaves like a pinhole camera.
+        let focus_dist = focus_dist.unwrap_or((lookfrom - lookat).length());
 
         let max_value: u8 = 255;
         let image_height: u32 = (image_width as f64 / aspect_ratio) as u32;
-        let focal_length: f64 = (lookfrom - lookat).length();
         let vfov: f64 = 20.0;
         let theta = vfov.to_radians();
         let h = (theta / 2.).tan();
 
-        let viewport_height = 2. * h * focal_length;
+        let viewport_height = 2. * h * focus_dist;
         let viewport_width: f64 = viewport_height * (image_width as f64 / image_height as f64);
 
         let center: DVec3 = lookfrom;
@@ -140,9 +230,14 @@ impl Camera {
 
         // Calculate the location of the upper left pixel.
         let viewport_upper_left: DVec3 =
-            center - (focal_length * w) - viewport_u / 2. - viewport_v / 2.;
+            center - (focus_dist * w) - viewport_u / 2. - viewport_v / 2.;
         let pixel00_loc: DVec3 = viewport_upper_left + 0.5 * (pixel_delta_u + pixel_delta_v);
 
+        // Calculate the defocus disk basis vectors.
+        let lens_radius = aperture / 2.0;
+        let defocus_disk_u = lens_radius * u;
+        let defocus_disk_v = lens_radius * v;
+
         Self {
             image_width,
             image_height,
@@ -162,46 +257,96 @@ impl Camera {
             u,
             v,
             w,
+            lens_radius,
+            defocus_disk_u,
+            defocus_disk_v,
+            time0,
+            time1,
+            background,
         }
     }
-    fn get_ray(&self, i: i32, j: i32) -> Ray {
+
+    /// Convenience constructor for a camera with a closed shutter (`time0 ==
+    /// time1 == 0.0`), so scenes without moving objects render identically
+    /// regardless of what shutter times `config` carries.
+    fn still(config: CameraConfig) -> Self {
+        Camera::new(config.shutter(0.0, 0.0))
+    }
+    fn get_ray(&self, i: i32, j: i32, rng: &mut impl Rng) -> Ray {
         // Get a randomly sampled camera ray for the pixel at location i,j.
 
         let pixel_center =
             self.pixel00_loc + (i as f64 * self.pixel_delta_u) + (j as f64 * self.pixel_delta_v);
-        let pixel_sample = pixel_center + self.pixel_sample_square();
+        let pixel_sample = pixel_center + self.pixel_sample_square(rng);
 
-        let ray_origin = self.center;
+        // Originate the ray from a random point on the defocus disk so points
+        // off the focal plane blur; a zero lens radius reduces to a pinhole.
+        let ray_origin = if self.lens_radius <= 0. {
+            self.center
+        } else {
+            self.defocus_disk_sample(rng)
+        };
         let ray_direction = pixel_sample - ray_origin;
 
+        // Sample a random time within the shutter interval for motion blur; a
+        // closed shutter (`time0 == time1`) pins every ray to that instant.
+        let time = if self.time0 < self.time1 {
+            rng.gen_range(self.time0..self.time1)
+        } else {
+            self.time0
+        };
+
         Ray {
-            origin: self.center,
+            origin: ray_origin,
             direction: ray_direction,
+            time,
         }
     }
 
-    fn pixel_sample_square(&self) -> DVec3 {
-        let mut rng = rand::thread_rng();
+    fn defocus_disk_sample(&self, rng: &mut impl Rng) -> DVec3 {
+        // Returns a random point on the camera defocus disk.
+        let p = random_in_unit_disk(rng);
+        self.center + (p.x * self.defocus_disk_u) + (p.y * self.defocus_disk_v)
+    }
+
+    fn pixel_sample_square(&self, rng: &mut impl Rng) -> DVec3 {
         // Returns a random point in the square surrounding a pixel at the origin.
         let px = -0.5 + rng.gen::<f64>();
         let py = -0.5 + rng.gen::<f64>();
         (px * self.pixel_delta_u) + (py * self.pixel_delta_v)
     }
-    fn render_to_disk<T>(&self, world: T) -> io::Result<()>
+    /// Render the scene and write it to `path`. The encoding is inferred from
+    /// the file extension: `.ppm` writes the textual P3 format directly (no
+    /// `image` dependency needed at runtime), anything else is handed to the
+    /// `image` crate, so `output.png` or `output.jpg` just work.
+    fn render_to_disk<T, P>(&self, world: T, path: P) -> io::Result<()>
     where
-        T: Hittable,
+        T: Hittable + Sync,
+        P: AsRef<Path>,
     {
+        let path = path.as_ref();
+
+        // Each pixel's color is independent, so the sampling loop is
+        // embarrassingly parallel; rayon distributes it across all cores while
+        // `collect` preserves the original row-major ordering. `map_init`
+        // gives each worker thread its own RNG instead of every sample/bounce
+        // reaching for the thread-local `rand::thread_rng()`.
         let pixels = (0..self.image_height)
             .cartesian_product(0..self.image_width)
+            .collect::<Vec<(u32, u32)>>()
+            .into_par_iter()
             .progress_count(self.image_height as u64 * self.image_width as u64)
-            .map(|(y, x)| {
+            .map_init(StdRng::from_entropy, |rng, (y, x)| {
                 let scale_factor = (self.samples_per_pixel as f64).recip();
 
                 let multisampled_pixel_color = (0..self.samples_per_pixel)
-                    .into_iter()
                     .map(|_| {
-                        self.get_ray(x as i32, y as i32)
-                            .color(self.max_depth as i32, &world)
+                        self.get_ray(x as i32, y as i32, rng).color(
+                            self.max_depth as i32,
+                            self.background,
+                            &world,
+                            rng,
+                        )
                     })
                     .sum::<DVec3>()
                     * scale_factor;
@@ -214,20 +359,45 @@ impl Camera {
                 }
                 .clamp(DVec3::splat(0.), DVec3::splat(0.999))
                     * 256.;
-                format!("{} {} {}", color.x, color.y, color.z)
+                (x, y, color)
             })
-            .join("\n");
-        fs::write(
-            "output.ppm",
-            format!(
-                "P3
+            .collect::<Vec<(u32, u32, DVec3)>>();
+
+        let is_ppm = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("ppm"))
+            .unwrap_or(false);
+
+        if is_ppm {
+            let body = pixels
+                .iter()
+                .map(|(_, _, color)| format!("{} {} {}", color.x, color.y, color.z))
+                .join("\n");
+            fs::write(
+                path,
+                format!(
+                    "P3
 {} {}
 {}
-{pixels}
+{body}
 ",
-                self.image_width, self.image_height, self.max_value
-            ),
-        )
+                    self.image_width, self.image_height, self.max_value
+                ),
+            )
+        } else {
+            let mut buffer: ImageBuffer<Rgb<u8>, Vec<u8>> =
+                ImageBuffer::new(self.image_width, self.image_height);
+            for (x, y, color) in &pixels {
+                buffer.put_pixel(
+                    *x,
+                    *y,
+                    Rgb([color.x as u8, color.y as u8, color.z as u8]),
+                );
+            }
+            buffer
+                .save(path)
+                .map_err(io::Error::other)
+        }
     }
 }
 
@@ -238,13 +408,15 @@ fn linear_to_gamma(scalar: f64) -> f64 {
 struct Ray {
     origin: DVec3,
     direction: DVec3,
+    /// Instant within the shutter interval at which the ray is cast
+    time: f64,
 }
 
 impl Ray {
     fn at(&self, t: f64) -> DVec3 {
         self.origin + t * self.direction
     }
-    fn color<T>(&self, depth: i32, world: &T) -> DVec3
+    fn color<T>(&self, depth: i32, background: Option<DVec3>, world: &T, rng: &mut impl Rng) -> DVec3
     where
         T: Hittable,
     {
@@ -252,14 +424,21 @@ impl Ray {
             return DVec3::new(0., 0., 0.);
         }
         if let Some(rec) = world.hit(&self, (0.001)..f64::INFINITY) {
+            let emitted = rec.material.emitted();
             if let Some(Scattered {
                 attenuation,
                 scattered,
-            }) = rec.material.scatter(self, rec.clone())
+            }) = rec.material.scatter(self, rec.clone(), rng)
             {
-                return attenuation * scattered.color(depth - 1, world);
+                return emitted + attenuation * scattered.color(depth - 1, background, world, rng);
             }
-            return DVec3::new(0., 0., 0.);
+            return emitted;
+        }
+
+        // A configured solid background lets emissive objects be the only light
+        // source; without one we fall back to the blue-white sky gradient.
+        if let Some(background) = background {
+            return background;
         }
 
         let unit_direction: DVec3 = self.direction.normalize();
@@ -270,6 +449,48 @@ impl Ray {
 
 trait Hittable {
     fn hit(&self, ray: &Ray, interval: Range<f64>) -> Option<HitRecord>;
+    /// Axis-aligned bounding box enclosing the object, used by the BVH.
+    fn bounding_box(&self) -> Aabb;
+}
+
+/// Axis-aligned bounding box, stored as the min/max corners per axis.
+#[derive(Clone)]
+struct Aabb {
+    minimum: DVec3,
+    maximum: DVec3,
+}
+impl Aabb {
+    fn new(minimum: DVec3, maximum: DVec3) -> Self {
+        Aabb { minimum, maximum }
+    }
+    /// Slab test: intersect the incoming `interval` with the `[t0, t1]` range
+    /// on each axis, returning false as soon as the range collapses.
+    fn hit(&self, ray: &Ray, interval: Range<f64>) -> bool {
+        let mut t_min = interval.start;
+        let mut t_max = interval.end;
+        for axis in 0..3 {
+            let inv_d = 1.0 / ray.direction[axis];
+            let mut t0 = (self.minimum[axis] - ray.origin[axis]) * inv_d;
+            let mut t1 = (self.maximum[axis] - ray.origin[axis]) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0, &mut t1);
+            }
+            t_min = t0.max(t_min);
+            t_max = t1.min(t_max);
+            if t_max <= t_min {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Smallest box enclosing both `box0` and `box1`.
+fn surrounding_box(box0: &Aabb, box1: &Aabb) -> Aabb {
+    Aabb::new(
+        box0.minimum.min(box1.minimum),
+        box0.maximum.max(box1.maximum),
+    )
 }
 
 #[non_exhaustive]
@@ -278,16 +499,17 @@ enum Material {
     Lambertian { albedo: DVec3 },
     Metal { albedo: DVec3, fuzz: f64 },
     Dielectric { index_of_refraction: f64 },
+    DiffuseLight { emit: DVec3 },
 }
 struct Scattered {
     attenuation: DVec3,
     scattered: Ray,
 }
 impl Material {
-    fn scatter(&self, r_in: &Ray, hit_record: HitRecord) -> Option<Scattered> {
+    fn scatter(&self, r_in: &Ray, hit_record: HitRecord, rng: &mut impl Rng) -> Option<Scattered> {
         match self {
             Material::Lambertian { albedo } => {
-                let mut scatter_direction = hit_record.normal + random_unit_vector();
+                let mut scatter_direction = hit_record.normal + random_unit_vector(rng);
 
                 // Catch degenerate scatter direction
                 if scatter_direction.abs_diff_eq(DVec3::new(0., 0., 0.), 1e-8) {
@@ -297,6 +519,7 @@ impl Material {
                 let scattered = Ray {
                     origin: hit_record.point,
                     direction: scatter_direction,
+                    time: r_in.time,
                 };
 
                 Some(Scattered {
@@ -308,7 +531,8 @@ impl Material {
                 let reflected: DVec3 = reflect(r_in.direction.normalize(), hit_record.normal);
                 let scattered = Ray {
                     origin: hit_record.point,
-                    direction: reflected + *fuzz * random_unit_vector(),
+                    direction: reflected + *fuzz * random_unit_vector(rng),
+                    time: r_in.time,
                 };
                 // absorb any scatter that is below the surface
                 if scattered.direction.dot(hit_record.normal) > 0. {
@@ -323,8 +547,6 @@ impl Material {
             Material::Dielectric {
                 index_of_refraction,
             } => {
-                let mut rng = rand::thread_rng();
-
                 let attenuation = DVec3::splat(1.0);
                 let refraction_ratio: f64 = if hit_record.front_face {
                     index_of_refraction.recip()
@@ -352,12 +574,22 @@ impl Material {
                     scattered: Ray {
                         origin: hit_record.point,
                         direction: direction,
+                        time: r_in.time,
                     },
                 })
             }
             _ => None,
         }
     }
+
+    /// Light emitted by the surface; only `DiffuseLight` emits, everything
+    /// else is black and contributes nothing.
+    fn emitted(&self) -> DVec3 {
+        match self {
+            Material::DiffuseLight { emit } => *emit,
+            _ => DVec3::ZERO,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -450,10 +682,77 @@ impl Hittable for Sphere {
 
         Some(rec)
     }
+
+    fn bounding_box(&self) -> Aabb {
+        let r = DVec3::splat(self.radius.abs());
+        Aabb::new(self.center - r, self.center + r)
+    }
+}
+
+struct MovingSphere {
+    center0: DVec3,
+    center1: DVec3,
+    time0: f64,
+    time1: f64,
+    radius: f64,
+    material: Material,
+}
+
+impl MovingSphere {
+    /// Linearly interpolated center at the given shutter time. A closed
+    /// shutter (`time0 == time1`) has no interval to interpolate across, so
+    /// it just pins the sphere to `center0` rather than dividing by zero.
+    fn center(&self, time: f64) -> DVec3 {
+        if self.time1 == self.time0 {
+            return self.center0;
+        }
+        self.center0
+            + ((time - self.time0) / (self.time1 - self.time0)) * (self.center1 - self.center0)
+    }
+}
+
+impl Hittable for MovingSphere {
+    fn hit(&self, ray: &Ray, interval: Range<f64>) -> Option<HitRecord> {
+        let center = self.center(ray.time);
+        let oc = ray.origin - center;
+        let a = ray.direction.length_squared();
+        let half_b = oc.dot(ray.direction);
+        let c = oc.length_squared() - self.radius * self.radius;
+
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < 0. {
+            return None;
+        }
+        let sqrtd = discriminant.sqrt();
+
+        // Find the nearest root that lies in the acceptable range.
+        let mut root = (-half_b - sqrtd) / a;
+        if !interval.contains(&root) {
+            root = (-half_b + sqrtd) / a;
+            if !interval.contains(&root) {
+                return None;
+            }
+        }
+
+        let t = root;
+        let point = ray.at(t);
+        let outward_normal = (point - center) / self.radius;
+
+        let rec = HitRecord::with_face_normal(self.material.clone(), point, outward_normal, t, ray);
+
+        Some(rec)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let r = DVec3::splat(self.radius.abs());
+        let box0 = Aabb::new(self.center0 - r, self.center0 + r);
+        let box1 = Aabb::new(self.center1 - r, self.center1 + r);
+        surrounding_box(&box0, &box1)
+    }
 }
 
 struct HittableList {
-    objects: Vec<Box<dyn Hittable>>,
+    objects: Vec<Box<dyn Hittable + Send + Sync>>,
 }
 impl HittableList {
     fn clear(&mut self) {
@@ -462,7 +761,7 @@ impl HittableList {
 
     fn add<T>(&mut self, object: T)
     where
-        T: Hittable + 'static,
+        T: Hittable + Send + Sync + 'static,
     {
         // was push_back
         self.objects.push(Box::new(object));
@@ -485,10 +784,94 @@ impl Hittable for HittableList {
 
         hit_record
     }
+
+    fn bounding_box(&self) -> Aabb {
+        self.objects
+            .iter()
+            .map(|object| object.bounding_box())
+            .reduce(|acc, bbox| surrounding_box(&acc, &bbox))
+            .unwrap_or(Aabb::new(DVec3::ZERO, DVec3::ZERO))
+    }
+}
+
+/// Bounding-volume hierarchy node; implements [`Hittable`] so it can stand in
+/// for a [`HittableList`] and turn per-ray cost from O(N) into roughly O(log N).
+struct BvhNode {
+    left: Box<dyn Hittable + Send + Sync>,
+    right: Option<Box<dyn Hittable + Send + Sync>>,
+    bbox: Aabb,
+}
+impl BvhNode {
+    /// Recursively partition `objects`: pick a random axis, sort by each box's
+    /// min coordinate on that axis, and split in half. Leaves hold one or two
+    /// objects.
+    fn new(mut objects: Vec<Box<dyn Hittable + Send + Sync>>) -> Self {
+        let axis = rand::thread_rng().gen_range(0..3);
+        // `objects`' element type is `Box<dyn Hittable + Send + Sync>`, so
+        // `sort_by` requires the comparator to take it by reference; there's
+        // no narrower type to borrow instead.
+        #[allow(clippy::borrowed_box)]
+        let comparator = |a: &Box<dyn Hittable + Send + Sync>, b: &Box<dyn Hittable + Send + Sync>| {
+            a.bounding_box().minimum[axis]
+                .partial_cmp(&b.bounding_box().minimum[axis])
+                .unwrap()
+        };
+
+        let (left, right): (
+            Box<dyn Hittable + Send + Sync>,
+            Option<Box<dyn Hittable + Send + Sync>>,
+        ) = match objects.len() {
+            0 => (Box::new(HittableList { objects: vec![] }), None),
+            1 => (objects.pop().unwrap(), None),
+            2 => {
+                objects.sort_by(comparator);
+                let right = objects.pop().unwrap();
+                let left = objects.pop().unwrap();
+                (left, Some(right))
+            }
+            _ => {
+                objects.sort_by(comparator);
+                let mid = objects.len() / 2;
+                let right_objects = objects.split_off(mid);
+                (
+                    Box::new(BvhNode::new(objects)),
+                    Some(Box::new(BvhNode::new(right_objects))),
+                )
+            }
+        };
+
+        let bbox = match &right {
+            Some(right) => surrounding_box(&left.bounding_box(), &right.bounding_box()),
+            None => left.bounding_box(),
+        };
+
+        BvhNode { left, right, bbox }
+    }
+}
+impl Hittable for BvhNode {
+    fn hit(&self, ray: &Ray, interval: Range<f64>) -> Option<HitRecord> {
+        if !self.bbox.hit(ray, interval.clone()) {
+            return None;
+        }
+
+        let hit_left = self.left.hit(ray, interval.clone());
+        // Narrow the far end of the interval with the nearer hit, exactly as
+        // the linear `HittableList::hit` fold does.
+        let end = hit_left.as_ref().map(|rec| rec.t).unwrap_or(interval.end);
+        let hit_right = match &self.right {
+            Some(right) => right.hit(ray, interval.start..end),
+            None => None,
+        };
+
+        hit_right.or(hit_left)
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        self.bbox.clone()
+    }
 }
 
-fn random_in_unit_sphere() -> DVec3 {
-    let mut rng = rand::thread_rng();
+fn random_in_unit_sphere(rng: &mut impl Rng) -> DVec3 {
     loop {
         let vec = DVec3::new(
             rng.gen_range(-1.0..1.),
@@ -502,12 +885,162 @@ fn random_in_unit_sphere() -> DVec3 {
     }
 }
 
-fn random_unit_vector() -> DVec3 {
-    return random_in_unit_sphere().normalize();
+/// Build the book's capstone "random spheres" scene: a large ground sphere,
+/// a grid of hundreds of small spheres with randomly assigned materials, and
+/// the three large feature spheres. The caller supplies the RNG so the layout
+/// can be made reproducible by seeding it (e.g. `StdRng::seed_from_u64`); the
+/// renderer's own sampling RNGs are not covered by this seed.
+fn random_scene(rng: &mut impl Rng) -> HittableList {
+    let mut world = HittableList { objects: vec![] };
+
+    let ground_material = Material::Lambertian {
+        albedo: DVec3::new(0.5, 0.5, 0.5),
+    };
+    world.add(Sphere {
+        center: DVec3::new(0., -1000., 0.),
+        radius: 1000.,
+        material: ground_material,
+    });
+
+    let feature_spheres = [
+        DVec3::new(0., 1., 0.),
+        DVec3::new(-4., 1., 0.),
+        DVec3::new(4., 1., 0.),
+    ];
+
+    for a in -11..11 {
+        for b in -11..11 {
+            let choose_mat = rng.gen::<f64>();
+            let center = DVec3::new(
+                a as f64 + 0.9 * rng.gen::<f64>(),
+                0.2,
+                b as f64 + 0.9 * rng.gen::<f64>(),
+            );
+
+            // Skip any small sphere whose center lies within 0.9 of a feature
+            // sphere's center; small spheres farther out can still touch one.
+            if feature_spheres
+                .iter()
+                .any(|feature| (center - *feature).length() < 0.9)
+            {
+                continue;
+            }
+
+            if choose_mat < 0.8 {
+                // Diffuse: albedo is the component-wise product of two randoms.
+                let albedo = DVec3::new(rng.gen::<f64>(), rng.gen::<f64>(), rng.gen::<f64>())
+                    * DVec3::new(rng.gen::<f64>(), rng.gen::<f64>(), rng.gen::<f64>());
+
+                // Every diffuse sphere bounces in place over the
+                // shutter interval, giving the BVH/parallel paths a realistic
+                // motion-blur workload to exercise.
+                let center1 = center + DVec3::new(0., rng.gen_range(0.0..0.5), 0.);
+                world.add(MovingSphere {
+                    center0: center,
+                    center1,
+                    time0: 0.0,
+                    time1: 1.0,
+                    radius: 0.2,
+                    material: Material::Lambertian { albedo },
+                });
+                continue;
+            }
+
+            let material = if choose_mat < 0.95 {
+                let albedo = DVec3::new(
+                    rng.gen_range(0.5..1.),
+                    rng.gen_range(0.5..1.),
+                    rng.gen_range(0.5..1.),
+                );
+                let fuzz = rng.gen_range(0.0..0.5);
+                Material::Metal { albedo, fuzz }
+            } else {
+                Material::Dielectric {
+                    index_of_refraction: 1.5,
+                }
+            };
+
+            world.add(Sphere {
+                center,
+                radius: 0.2,
+                material,
+            });
+        }
+    }
+
+    world.add(Sphere {
+        center: DVec3::new(0., 1., 0.),
+        radius: 1.0,
+        material: Material::Dielectric {
+            index_of_refraction: 1.5,
+        },
+    });
+    world.add(Sphere {
+        center: DVec3::new(-4., 1., 0.),
+        radius: 1.0,
+        material: Material::Lambertian {
+            albedo: DVec3::new(0.4, 0.2, 0.1),
+        },
+    });
+    world.add(Sphere {
+        center: DVec3::new(4., 1., 0.),
+        radius: 1.0,
+        material: Material::Metal {
+            albedo: DVec3::new(0.7, 0.6, 0.5),
+            fuzz: 0.0,
+        },
+    });
+
+    world
+}
+
+/// A small scene lit only by a glowing `DiffuseLight` sphere hovering over a
+/// ground sphere, meant to be paired with a black `Camera` background so the
+/// light is the only source of illumination.
+fn emissive_scene() -> HittableList {
+    let mut world = HittableList { objects: vec![] };
+
+    world.add(Sphere {
+        center: DVec3::new(0., -1000., 0.),
+        radius: 1000.,
+        material: Material::Lambertian {
+            albedo: DVec3::new(0.5, 0.5, 0.5),
+        },
+    });
+    world.add(Sphere {
+        center: DVec3::new(0., 2., 0.),
+        radius: 2.,
+        material: Material::Lambertian {
+            albedo: DVec3::new(0.4, 0.2, 0.1),
+        },
+    });
+    world.add(Sphere {
+        center: DVec3::new(0., 7., 0.),
+        radius: 2.,
+        material: Material::DiffuseLight {
+            emit: DVec3::splat(4.0),
+        },
+    });
+
+    world
+}
+
+fn random_in_unit_disk(rng: &mut impl Rng) -> DVec3 {
+    loop {
+        let p = DVec3::new(rng.gen_range(-1.0..1.), rng.gen_range(-1.0..1.), 0.);
+
+        if p.length_squared() < 1. {
+            break p;
+        }
+    }
+}
+
+fn random_unit_vector(rng: &mut impl Rng) -> DVec3 {
+    return random_in_unit_sphere(rng).normalize();
 }
 
-fn random_on_hemisphere(normal: &DVec3) -> DVec3 {
-    let on_unit_sphere = random_unit_vector();
+fn random_on_hemisphere(normal: &DVec3, rng: &mut impl Rng) -> DVec3 {
+    let on_unit_sphere = random_unit_vector(rng);
     if on_unit_sphere.dot(*normal) > 0.0
     // In the same hemisphere as the normal
     {